@@ -0,0 +1,136 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Local, TimeDelta};
+use serde::Deserialize;
+
+/// Name of the environment variable that points to the schedule config file.
+const SCHEDULE_CONFIG_PATH_VAR: &str = "SCHEDULE_CONFIG_PATH";
+/// Name of the environment variable overriding the git remote to use. Defaults to `origin`.
+const GIT_REMOTE_VAR: &str = "GIT_REMOTE";
+/// Name of the environment variable overriding the git branch to use. Defaults to `main`.
+const GIT_BRANCH_VAR: &str = "GIT_BRANCH";
+
+/// Error produced while loading or parsing the [ScheduleConfig].
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    /// The environment variable pointing to the config file is missing.
+    #[error("Environment variable '{0}' missing")]
+    EnvVarMissing(&'static str),
+    /// The config file couldn't be read.
+    #[error("Failed to read config file {0:?}: {1}")]
+    Read(PathBuf, std::io::Error),
+    /// The config file content isn't valid TOML or doesn't match the expected structure.
+    #[error("Failed to parse config file {0:?}: {1}")]
+    Parse(PathBuf, toml::de::Error),
+    /// The `anchor` field of the config file isn't a valid RFC 3339 timestamp.
+    #[error("Failed to parse anchor timestamp {0:?} in config file {1:?}: {2}")]
+    InvalidAnchor(String, PathBuf, chrono::ParseError),
+    /// The configured interval isn't positive, which would make the rotation divide by
+    /// zero (or go backwards) when looking up the current sheet.
+    #[error("Interval in config file {0:?} must be positive, but was {1} weeks / {2} days")]
+    NonPositiveInterval(PathBuf, i64, i64),
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSchedule {
+    anchor: String,
+    interval_weeks: Option<i64>,
+    interval_days: Option<i64>,
+    sheets: Vec<String>,
+}
+
+/// An ordered rotation of sheet names, anchored at a start timestamp and advancing by a
+/// fixed interval.
+///
+/// Loaded from the TOML file pointed to by the `SCHEDULE_CONFIG_PATH` environment variable,
+/// so the rotation can be changed without recompiling.
+#[derive(Debug)]
+pub struct ScheduleConfig {
+    anchor: DateTime<Local>,
+    interval: TimeDelta,
+    sheets: Vec<String>,
+}
+
+impl ScheduleConfig {
+    /// Load the schedule from the TOML file pointed to by the `SCHEDULE_CONFIG_PATH`
+    /// environment variable.
+    pub fn from_env() -> Result<ScheduleConfig, ConfigError> {
+        let path = env::var(SCHEDULE_CONFIG_PATH_VAR)
+            .map_err(|_| ConfigError::EnvVarMissing(SCHEDULE_CONFIG_PATH_VAR))?;
+        Self::from_path(path)
+    }
+
+    /// Load and parse the schedule from the given TOML file.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<ScheduleConfig, ConfigError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::Read(path.to_path_buf(), e))?;
+        let raw: RawSchedule =
+            toml::from_str(&content).map_err(|e| ConfigError::Parse(path.to_path_buf(), e))?;
+
+        let anchor = DateTime::parse_from_rfc3339(&raw.anchor)
+            .map(|dt| dt.with_timezone(&Local))
+            .map_err(|e| ConfigError::InvalidAnchor(raw.anchor.clone(), path.to_path_buf(), e))?;
+
+        let interval = match (raw.interval_weeks, raw.interval_days) {
+            (Some(weeks), _) => TimeDelta::weeks(weeks),
+            (None, Some(days)) => TimeDelta::days(days),
+            (None, None) => TimeDelta::weeks(1),
+        };
+
+        if interval.num_seconds() <= 0 {
+            return Err(ConfigError::NonPositiveInterval(
+                path.to_path_buf(),
+                raw.interval_weeks.unwrap_or(0),
+                raw.interval_days.unwrap_or(0),
+            ));
+        }
+
+        Ok(ScheduleConfig {
+            anchor,
+            interval,
+            sheets: raw.sheets,
+        })
+    }
+
+    /// Returns the name of the sheet that is currently active, or `None` once the current
+    /// time is past the end of the configured rotation so the caller can shut down cleanly.
+    ///
+    /// Before `anchor` the rotation hasn't started yet, so the first sheet is returned
+    /// rather than `None` (e.g. deploying ahead of a future rotation's start is normal and
+    /// shouldn't be treated the same as the rotation having ended).
+    pub fn get_current_sheet_name(&self) -> Option<&str> {
+        let current = Local::now();
+        if current < self.anchor {
+            return self.sheets.first().map(String::as_str);
+        }
+
+        let elapsed = current - self.anchor;
+        let index = (elapsed.num_seconds() / self.interval.num_seconds()) as usize;
+        self.sheets.get(index).map(String::as_str)
+    }
+}
+
+/// The git remote and branch to pull from and push to.
+///
+/// Resolved once at startup from the `GIT_REMOTE`/`GIT_BRANCH` environment variables
+/// (falling back to `origin`/`main`) and reused for all server commands, instead of
+/// hardcoding `origin main` everywhere.
+#[derive(Debug, Clone)]
+pub struct RemoteRef {
+    pub remote: String,
+    pub branch: String,
+}
+
+impl RemoteRef {
+    /// Resolve the remote and branch from the environment, falling back to `origin`/`main`.
+    pub fn from_env() -> RemoteRef {
+        RemoteRef {
+            remote: env::var(GIT_REMOTE_VAR).unwrap_or_else(|_| "origin".to_string()),
+            branch: env::var(GIT_BRANCH_VAR).unwrap_or_else(|_| "main".to_string()),
+        }
+    }
+}
@@ -1,8 +1,6 @@
-use std::{
-    path::Path,
-    process::{Command, ExitStatus},
-    rc::Rc,
-};
+use std::{path::Path, process::Command, rc::Rc};
+
+use crate::config::RemoteRef;
 
 /// This wraps the error that occurs when a git command failed to execute.
 /// It doesn't include rate-limiting, for this see [GitInteractionError].
@@ -12,15 +10,39 @@ pub enum GitCommandError {
     #[error("Failed to execute git command {0}")]
     Exec(#[from] std::io::Error),
     /// The command ran but exited with a non-zero status code
-    #[error("Unknown git error occured: status={0}, stderr={1:?}")]
-    Other(ExitStatus, Rc<str>),
+    #[error("Git command `{cmd}` failed (exit code {code:?}): {stderr}")]
+    Other {
+        /// The reconstructed command line, e.g. `git push origin main`.
+        cmd: String,
+        /// The process' exit code, if it terminated normally.
+        code: Option<i32>,
+        stderr: Rc<str>,
+    },
+    /// The command failed because another process is holding `.git/index.lock`, e.g. a
+    /// leftover from a previously killed git invocation.
+    #[error("Git index lock is held: {0:?}")]
+    LockHeld(Rc<str>),
+}
+
+/// Returns `true` if `stderr` indicates that the command failed because of a stale
+/// `.git/index.lock`, rather than a real git error.
+fn is_lock_held(stderr: &str) -> bool {
+    stderr.contains("index.lock") || stderr.contains("Unable to create '.git/index.lock'")
+}
+
+/// Reconstructs the command line of `command` (program + args) for error messages.
+fn format_command(command: &Command) -> String {
+    std::iter::once(command.get_program().to_string_lossy().into_owned())
+        .chain(command.get_args().map(|arg| arg.to_string_lossy().into_owned()))
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 /// Error wrapping git commands that communicate with the server.
-/// 
-/// If a git push/pull command failes, the error message is scanned for the patterns
-/// `"Connection refused"` and `"ssh:"`. If both are found the error is considered
-/// a rate limitation.
+///
+/// If a git push/pull command fails, the error message is scanned against
+/// [Opts::rate_limit_patterns]. If all patterns are found the error is considered a rate
+/// limitation instead of a hard failure.
 #[derive(Debug, thiserror::Error)]
 pub enum GitInteractionError {
     /// The git command failed but it doesn't seem to be the rate limit that caused it.
@@ -31,7 +53,57 @@ pub enum GitInteractionError {
     LimitReached(Rc<str>),
 }
 
-fn run_git_server_command(folder: &Path, command: &mut Command) -> Result<(), GitInteractionError> {
+/// Configures how [run_git_server_command] classifies the stderr of a failed git
+/// invocation that talks to the server.
+#[derive(Debug, Clone)]
+pub struct Opts {
+    /// stderr substrings that mark an otherwise failed command as a (silent) success,
+    /// e.g. known-harmless warnings some git/ssh versions print on stderr.
+    pub ignored_errors: Vec<String>,
+    /// stderr substrings that, when ALL present, classify the failure as a rate limit
+    /// rather than a hard error. Defaults to `["Connection refused", "ssh:"]`.
+    pub rate_limit_patterns: Vec<String>,
+}
+
+impl Default for Opts {
+    fn default() -> Opts {
+        Opts {
+            ignored_errors: Vec::new(),
+            rate_limit_patterns: vec!["Connection refused".to_string(), "ssh:".to_string()],
+        }
+    }
+}
+
+impl Opts {
+    /// Resolve from the comma-separated `GIT_IGNORED_ERRORS`/`GIT_RATE_LIMIT_PATTERNS`
+    /// environment variables, falling back to [Opts::default] for whichever is unset.
+    pub fn from_env() -> Opts {
+        fn split_env(var: &str) -> Option<Vec<String>> {
+            std::env::var(var).ok().map(|value| {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+        }
+
+        let defaults = Opts::default();
+        Opts {
+            ignored_errors: split_env("GIT_IGNORED_ERRORS").unwrap_or(defaults.ignored_errors),
+            rate_limit_patterns: split_env("GIT_RATE_LIMIT_PATTERNS")
+                .unwrap_or(defaults.rate_limit_patterns),
+        }
+    }
+}
+
+fn run_git_server_command(
+    folder: &Path,
+    command: &mut Command,
+    opts: &Opts,
+) -> Result<(), GitInteractionError> {
+    let cmd = format_command(command);
     let output = command
         .current_dir(folder)
         .output()
@@ -39,29 +111,138 @@ fn run_git_server_command(folder: &Path, command: &mut Command) -> Result<(), Gi
 
     // let stdout = String::from_utf8(output.stdout);
     if output.status.success() {
-        Ok(())
-    } else {
-        let stderr: Rc<str> = String::from_utf8_lossy(&output.stderr).into();
-        Err(
-            if stderr.contains("Connection refused") && stderr.contains("ssh:") {
-                GitInteractionError::LimitReached(stderr)
-            } else {
-                GitCommandError::Other(output.status, stderr).into()
-            },
-        )
+        return Ok(());
+    }
+
+    let stderr: Rc<str> = String::from_utf8_lossy(&output.stderr).into();
+
+    if opts
+        .ignored_errors
+        .iter()
+        .any(|pattern| stderr.contains(pattern.as_str()))
+    {
+        return Ok(());
     }
+
+    if is_lock_held(&stderr) {
+        return Err(GitCommandError::LockHeld(stderr).into());
+    }
+
+    Err(
+        if opts
+            .rate_limit_patterns
+            .iter()
+            .all(|pattern| stderr.contains(pattern.as_str()))
+        {
+            GitInteractionError::LimitReached(stderr)
+        } else {
+            GitCommandError::Other {
+                cmd,
+                code: output.status.code(),
+                stderr,
+            }
+            .into()
+        },
+    )
 }
 pub fn run_git_local_command(folder: &Path, command: &mut Command) -> Result<(), GitCommandError> {
+    let cmd = format_command(command);
     let output = command.current_dir(folder).output()?;
 
     if output.status.success() {
         Ok(())
     } else {
         let stderr: Rc<str> = String::from_utf8_lossy(&output.stderr).into();
-        Err(GitCommandError::Other(output.status, stderr))
+        if is_lock_held(&stderr) {
+            Err(GitCommandError::LockHeld(stderr))
+        } else {
+            Err(GitCommandError::Other {
+                cmd,
+                code: output.status.code(),
+                stderr,
+            })
+        }
     }
 }
 
+/// The kind of change reported by `git status --porcelain` for a path, derived from the
+/// `M`/`A`/`D`/`??` status codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Modified,
+    Added,
+    Deleted,
+    Untracked,
+}
+
+impl ChangeKind {
+    fn from_code(code: char) -> Option<ChangeKind> {
+        match code {
+            'M' => Some(ChangeKind::Modified),
+            'A' => Some(ChangeKind::Added),
+            'D' => Some(ChangeKind::Deleted),
+            _ => None,
+        }
+    }
+}
+
+/// One line of `git status --porcelain` output, split into its staged (index) and
+/// unstaged (working tree) columns.
+#[derive(Debug, Clone)]
+pub struct StatusItem {
+    pub path: String,
+    pub staged: Option<ChangeKind>,
+    pub unstaged: Option<ChangeKind>,
+}
+
+/// run `git status --porcelain` and parse the output into structured [StatusItem]s, one
+/// per reported path.
+pub fn git_status(folder: &Path) -> Result<Vec<StatusItem>, GitCommandError> {
+    let mut command = Command::new("git");
+    command.args(["status", "--porcelain"]);
+    let cmd = format_command(&command);
+    let output = command.current_dir(folder).output()?;
+
+    if !output.status.success() {
+        let stderr: Rc<str> = String::from_utf8_lossy(&output.stderr).into();
+        return Err(if is_lock_held(&stderr) {
+            GitCommandError::LockHeld(stderr)
+        } else {
+            GitCommandError::Other {
+                cmd,
+                code: output.status.code(),
+                stderr,
+            }
+        });
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .filter_map(|line| {
+            let mut columns = line.chars();
+            let staged_code = columns.next()?;
+            let unstaged_code = columns.next()?;
+            let path = line.get(3..)?.to_string();
+
+            let (staged, unstaged) = if staged_code == '?' && unstaged_code == '?' {
+                (None, Some(ChangeKind::Untracked))
+            } else {
+                (
+                    ChangeKind::from_code(staged_code),
+                    ChangeKind::from_code(unstaged_code),
+                )
+            };
+
+            Some(StatusItem {
+                path,
+                staged,
+                unstaged,
+            })
+        })
+        .collect())
+}
+
 /// run `git reset --hard`
 pub fn run_git_reset_files(folder: &Path) -> Result<(), GitCommandError> {
     run_git_local_command(folder, Command::new("git").args(["reset", "--hard"]))
@@ -89,18 +270,28 @@ pub fn run_git_commit(folder: &Path, sheet_name: &str) -> Result<(), GitCommandE
     )
 }
 
-/// run `git pull origin main`
-pub fn run_git_pull<P: AsRef<Path>>(folder: P) -> Result<(), GitInteractionError> {
+/// run `git pull <remote> <branch>`
+pub fn run_git_pull<P: AsRef<Path>>(
+    folder: P,
+    remote: &RemoteRef,
+    opts: &Opts,
+) -> Result<(), GitInteractionError> {
     run_git_server_command(
         folder.as_ref(),
-        Command::new("git").args(["pull", "origin", "main"]),
+        Command::new("git").args(["pull", &remote.remote, &remote.branch]),
+        opts,
     )
 }
 
-/// run `git push origin main`
-pub fn run_git_push<P: AsRef<Path>>(folder: P) -> Result<(), GitInteractionError> {
+/// run `git push <remote> <branch>`
+pub fn run_git_push<P: AsRef<Path>>(
+    folder: P,
+    remote: &RemoteRef,
+    opts: &Opts,
+) -> Result<(), GitInteractionError> {
     run_git_server_command(
         folder.as_ref(),
-        Command::new("git").args(["push", "origin", "main"]),
+        Command::new("git").args(["push", &remote.remote, &remote.branch]),
+        opts,
     )
 }
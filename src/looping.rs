@@ -1,7 +1,8 @@
-use std::{convert::Infallible, path::Path};
+use std::path::Path;
 
 use crate::{
-    git_interaction::run_git_reset_commit,
+    config::RemoteRef,
+    git_interaction::{Opts, run_git_reset_commit},
     new_push::{GitCommandError, GitInteractionError},
     waiting::{IntelligentWait, WaitingGaveUp},
 };
@@ -20,33 +21,69 @@ pub enum Error {
 
 /// Main application loop.
 /// This loop will continuosly make changes and upload those to the server.
-/// 
+///
 /// After each upload the instance of [IntelligentWait] provided as parameter
 /// is used to delay the next run of the loop depending of the current upload's status.
 ///
-/// This function will only return if a fatal error occurs.
-pub fn update_loop<W: IntelligentWait, I: AsRef<str>, P: Fn() -> I>(
+/// This function returns `Ok(())` once `get_process_run_label` reports that no sheet is
+/// currently active (the schedule is exhausted), and only returns an `Err` if a fatal
+/// error occurs.
+pub fn update_loop<W: IntelligentWait, I: AsRef<str>, P: Fn() -> Option<I>>(
     path: impl AsRef<Path>,
     mut wait_after: W,
     get_process_run_label: P,
-) -> Result<Infallible, Error> {
+    remote: RemoteRef,
+    opts: Opts,
+) -> Result<(), Error> {
+    /// Maximum number of times a stale `.git/index.lock` is automatically removed and
+    /// retried for the same run before falling through to the regular error backoff.
+    const MAX_LOCK_RETRIES: u32 = 3;
+
     let mut inner = |path: &Path| {
         let mut maybe_diverged = false;
+        let mut consecutive_lock_retries: u32 = 0;
         loop {
-            let _sheet = get_process_run_label();
+            let Some(_sheet) = get_process_run_label() else {
+                log::info!("No sheet is currently active according to the schedule. Shutting down.");
+                return Ok(());
+            };
             let sheet = _sheet.as_ref();
             log::info!("Start new upload process for {sheet}");
-            let res = crate::new_push::cause_new_run(path, &sheet, maybe_diverged);
+            let res = crate::new_push::cause_new_run(path, &sheet, maybe_diverged, &remote, &opts);
             match res {
                 Ok(true) => {
                     log::info!("Upload process succeeded!");
+                    consecutive_lock_retries = 0;
                     wait_after.success();
                 }
                 Ok(false) => {
                     log::info!("Process skipped as nothing is to do!");
+                    consecutive_lock_retries = 0;
                     wait_after.skipped();
                 }
+                Err(err) if err.is_lock_held() && consecutive_lock_retries < MAX_LOCK_RETRIES => {
+                    consecutive_lock_retries += 1;
+                    log::warn!(
+                        "Stale .git/index.lock detected ({err}). Removing it and retrying this run ({consecutive_lock_retries}/{MAX_LOCK_RETRIES})."
+                    );
+                    let lock_path = path.join(".git").join("index.lock");
+                    if let Err(remove_err) = std::fs::remove_file(&lock_path) {
+                        if remove_err.kind() != std::io::ErrorKind::NotFound {
+                            log::error!(
+                                "Failed to remove stale lock file {lock_path:?}: {remove_err}"
+                            );
+                        }
+                    }
+                }
+                Err(err) if err.is_lock_held() => {
+                    log::error!(
+                        ".git/index.lock still present after {MAX_LOCK_RETRIES} removal attempts, likely a concurrent git process rather than a stale lock. Treating as a regular error."
+                    );
+                    consecutive_lock_retries = 0;
+                    wait_after.error()?;
+                }
                 Err(err) => {
+                    consecutive_lock_retries = 0;
                     use crate::new_push::NewRunError;
                     match err {
                         NewRunError::LimitReached(_) => {
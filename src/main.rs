@@ -2,13 +2,17 @@
 
 use std::{path::PathBuf, process::exit, str::FromStr, time::Duration};
 
+mod config;
 pub(crate) mod git_interaction;
 mod looping;
 mod new_push;
-mod sheet_name;
+mod triggering;
 mod waiting;
+use config::{RemoteRef, ScheduleConfig};
+use git_interaction::Opts;
 use looping::update_loop;
-use waiting::DefaultWaiter;
+use triggering::FileWatchTrigger;
+use waiting::{DefaultWaiter, IntelligentWait};
 
 fn main() {
     dotenvy::dotenv().unwrap();
@@ -20,17 +24,48 @@ fn main() {
         log::error!("The provided path doesn't exist {path:?}. Program will terminate");
         exit(2);
     }
+
+    let schedule = ScheduleConfig::from_env().unwrap_or_else(|err| {
+        log::error!("Failed to load schedule config. Program will terminate: {err}");
+        exit(2);
+    });
+
+    let after_success = Duration::from_secs(7);
+    let after_error = Duration::from_secs(5 * 60);
+    let after_skipped = Duration::from_secs(30 * 60);
+    let max_error_retry = 10;
+
+    let wait_after: Box<dyn IntelligentWait> = if std::env::var("WATCH_MODE").is_ok() {
+        log::info!("WATCH_MODE enabled, watching {path:?} for file changes instead of polling on a fixed interval");
+        let trigger = FileWatchTrigger::new(&path, Duration::from_secs(2)).unwrap_or_else(|err| {
+            log::error!("Failed to start watching {path:?} for file changes: {err}");
+            exit(2);
+        });
+        Box::new(DefaultWaiter::with_trigger(
+            after_success,
+            after_error,
+            after_skipped,
+            max_error_retry,
+            trigger,
+        ))
+    } else {
+        Box::new(DefaultWaiter::new(
+            after_success,
+            after_error,
+            after_skipped,
+            max_error_retry,
+        ))
+    };
+
     let res = update_loop(
         &path,
-        DefaultWaiter::new(
-            Duration::from_secs(7),
-            Duration::from_secs(5 * 60),
-            Duration::from_secs(30 * 60),
-            10,
-        ),
-        sheet_name::get_current_sheet_name,
+        wait_after,
+        || schedule.get_current_sheet_name(),
+        RemoteRef::from_env(),
+        Opts::from_env(),
     );
-    let Err(err) = res;
-    log::error!("End of program reached: {err}");
-    exit(1);
+    if let Err(err) = res {
+        log::error!("End of program reached: {err}");
+        exit(1);
+    }
 }
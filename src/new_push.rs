@@ -1,9 +1,10 @@
 use std::{path::Path, rc::Rc, time::Duration};
 
 pub use crate::git_interaction::{
-    GitCommandError, GitInteractionError, run_git_pull, run_git_push,
+    GitCommandError, GitInteractionError, Opts, run_git_pull, run_git_push,
 };
-use crate::git_interaction::{run_git_add_all, run_git_commit, run_git_reset_files};
+use crate::config::RemoteRef;
+use crate::git_interaction::{git_status, run_git_add_all, run_git_commit, run_git_reset_files};
 
 #[derive(Debug, thiserror::Error)]
 pub enum NewRunError {
@@ -17,11 +18,29 @@ pub enum NewRunError {
     MakeChanges(#[from] std::io::Error),
     #[error("Adding new changes failed: {0}")]
     AddAll(GitCommandError),
+    #[error("Checking git status failed: {0}")]
+    Status(GitCommandError),
     #[error("Committing new changes failed: {0}")]
     Commit(GitCommandError),
     #[error("Pushing new changes failed: {0}")]
     Push(GitInteractionError),
 }
+impl NewRunError {
+    /// Returns `true` if this error was caused by a stale `.git/index.lock` rather than a
+    /// real git failure, so the caller can remove it and retry the run instead of treating
+    /// it as fatal.
+    pub fn is_lock_held(&self) -> bool {
+        matches!(
+            self,
+            NewRunError::ResetFiles(GitCommandError::LockHeld(_))
+                | NewRunError::Pull(GitCommandError::LockHeld(_))
+                | NewRunError::AddAll(GitCommandError::LockHeld(_))
+                | NewRunError::Status(GitCommandError::LockHeld(_))
+                | NewRunError::Commit(GitCommandError::LockHeld(_))
+                | NewRunError::Push(GitInteractionError::Exec(GitCommandError::LockHeld(_)))
+        )
+    }
+}
 fn new_run_err(
     specific: impl FnOnce(GitCommandError) -> NewRunError,
 ) -> impl FnOnce(GitInteractionError) -> NewRunError {
@@ -92,16 +111,22 @@ pub fn make_changes(folder: &Path, sheet_name: &str) -> Result<bool, std::io::Er
 /// If the previous push failed an optional pull can be executed.
 /// This can fix the history when another instance independently pushed changes to the server.
 /// After this optional pull the program will wait 10s, just to mititage rate limiting.
+///
+/// After staging, `git status --porcelain` is checked and the commit and push are skipped
+/// (returning `Ok(false)`) if nothing ended up staged, e.g. because the swap in
+/// [make_changes] was a no-op.
 pub fn cause_new_run(
     folder: &Path,
     sheet_name: &str,
     prepend_pull: bool,
+    remote: &RemoteRef,
+    opts: &Opts,
 ) -> Result<bool, NewRunError> {
     log::debug!("Start causing new server run...");
     run_git_reset_files(folder).map_err(NewRunError::ResetFiles)?;
     log::debug!("git reset local directory");
     if prepend_pull {
-        run_git_pull(folder).map_err(new_run_err(NewRunError::Pull))?;
+        run_git_pull(folder, remote, opts).map_err(new_run_err(NewRunError::Pull))?;
         log::info!("git pull from remote");
     }
 
@@ -111,6 +136,15 @@ pub fn cause_new_run(
 
     run_git_add_all(folder).map_err(NewRunError::AddAll)?;
     log::debug!("git add local changes");
+
+    let status = git_status(folder).map_err(NewRunError::Status)?;
+    if !status.iter().any(|item| item.staged.is_some()) {
+        log::debug!(
+            "git status shows no staged changes after swapping files; skipping commit and push"
+        );
+        return Ok(false);
+    }
+
     run_git_commit(folder, sheet_name).map_err(NewRunError::Commit)?;
 
     if prepend_pull {
@@ -121,7 +155,7 @@ pub fn cause_new_run(
     } else {
         log::debug!("git commit local changes");
     }
-    run_git_push(folder).map_err(NewRunError::Push)?;
+    run_git_push(folder, remote, opts).map_err(NewRunError::Push)?;
     log::debug!("git push to remote");
     log::debug!("Causing rerun succeeded!");
     Ok(true)
@@ -0,0 +1,132 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::{Receiver, channel},
+    time::{Duration, Instant},
+};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Something that can wait up to a given duration for the next run to start, instead of
+/// always sleeping the full duration.
+///
+/// Used by [crate::waiting::DefaultWaiter] for the normal polling cadence (`success`/
+/// `skipped`), alongside the existing fixed-interval behaviour.
+pub trait Trigger {
+    /// Wait for the next run to be due, normally for `duration`. Implementations may return
+    /// earlier than `duration` if something more specific happens first, and may add a
+    /// short settling period of their own (see [FileWatchTrigger]).
+    fn wait(&self, status: &str, duration: Duration);
+}
+
+/// Always waits the full duration using [std::thread::sleep]. This is the historical,
+/// fixed-interval polling behaviour.
+#[derive(Debug, Default)]
+pub struct TimedTrigger;
+
+impl Trigger for TimedTrigger {
+    fn wait(&self, status: &str, duration: Duration) {
+        log::debug!("[status={status}] wait {duration:?} till next run.");
+        std::thread::sleep(duration);
+    }
+}
+
+fn is_relevant_change(event: &Event, excluded: &Path) -> bool {
+    matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_))
+        && event.paths.iter().any(|p| !p.starts_with(excluded))
+}
+
+/// Watches a folder for filesystem changes using the `notify` crate's recommended
+/// watcher and wakes up as soon as a relevant `Modify`/`Create` event arrives, instead of
+/// waiting the full duration. Bursts of events are debounced over a short window so a
+/// series of saves only triggers a single run. If nothing changes it waits the full
+/// duration, after the one debounce window spent discarding earlier events (see below), so callers like rate-limit backoff (which don't use this trigger) are
+/// unaffected and the normal cadence still acts as a safety net.
+///
+/// Events under the watched folder's `.git` directory are ignored, since
+/// [crate::new_push::cause_new_run] itself writes heavily there (`index`, `index.lock`,
+/// `HEAD`, refs, new objects) on every run; without this exclusion the tool would
+/// re-trigger on its own git activity and turn into a tight, rate-limit-burning loop.
+///
+/// [crate::new_push::make_changes] also rewrites the sheet's own `.cpp`/`.other` files on
+/// every run, outside of `.git`, which the path exclusion above doesn't cover. Since
+/// [Trigger::wait] is only called once that run has already finished, any events those
+/// writes caused are already queued by the time waiting starts; [FileWatchTrigger::wait]
+/// discards such a backlog before listening for a genuinely new change, so the tool
+/// doesn't immediately re-trigger on its own last write.
+pub struct FileWatchTrigger {
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    debounce: Duration,
+    excluded: PathBuf,
+}
+
+impl FileWatchTrigger {
+    /// Start watching `path` recursively. `debounce` is the quiet period required after
+    /// the last relevant event before a run is triggered.
+    pub fn new(path: impl AsRef<Path>, debounce: Duration) -> notify::Result<FileWatchTrigger> {
+        let (tx, events) = channel();
+        let mut watcher = notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })?;
+        watcher.watch(path.as_ref(), RecursiveMode::Recursive)?;
+        Ok(FileWatchTrigger {
+            _watcher: watcher,
+            events,
+            debounce,
+            excluded: path.as_ref().join(".git"),
+        })
+    }
+
+    /// Discard events for one full `debounce` window: those already queued (e.g. caused by
+    /// the run that just finished writing the sheet's files) and any arriving within that
+    /// window are dropped instead of being treated as a new, user-triggered change.
+    fn drain_pending_events(&self) {
+        let deadline = Instant::now() + self.debounce;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return;
+            }
+            if self.events.recv_timeout(remaining).is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Wait up to `timeout` for a relevant file change, returning the path it touched.
+    fn wait_for_relevant_event(&self, timeout: Duration) -> Option<PathBuf> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return None;
+            }
+            match self.events.recv_timeout(remaining) {
+                Ok(Ok(event)) if is_relevant_change(&event, &self.excluded) => {
+                    return event
+                        .paths
+                        .into_iter()
+                        .find(|p| !p.starts_with(&self.excluded));
+                }
+                Ok(_) => continue,
+                Err(_) => return None,
+            }
+        }
+    }
+}
+
+impl Trigger for FileWatchTrigger {
+    fn wait(&self, status: &str, duration: Duration) {
+        self.drain_pending_events();
+
+        log::debug!(
+            "[status={status}] waiting up to {duration:?} for a relevant file change under the watched path."
+        );
+        let Some(changed) = self.wait_for_relevant_event(duration) else {
+            log::debug!("No relevant file change within {duration:?}, continuing with the fixed schedule.");
+            return;
+        };
+        log::debug!("Detected a relevant file change ({changed:?}), debouncing {:?} before the next run.", self.debounce);
+        while self.wait_for_relevant_event(self.debounce).is_some() {}
+    }
+}
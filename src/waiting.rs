@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use crate::triggering::{TimedTrigger, Trigger};
+
 /// The waiting implementation returns this error to indicate that the program should end
 /// due to too many errors, so waiting longer would be pointless.
 #[derive(Debug, thiserror::Error)]
@@ -10,7 +12,7 @@ pub enum WaitingGaveUp {
 }
 
 /// This trait can be implemented for objects that can wait between different runs depending on the current status.
-/// 
+///
 /// The implementation is free to choose any waiting duration and have internal counters to measure errors.
 pub trait IntelligentWait {
     /// The last run succeeded, wait a specific time.
@@ -26,24 +28,72 @@ pub trait IntelligentWait {
         log::debug!("[status={status}] wait {duration:?} till next run.");
         std::thread::sleep(duration);
     }
+    /// Used by [Self::success] and [Self::skipped] for the normal polling cadence.
+    /// Defaults to [Self::_wait], but can be overridden to wait on a [Trigger] instead,
+    /// e.g. to react to file changes immediately rather than a fixed interval. Unlike
+    /// [Self::_wait], this is never used for rate-limit or error backoff, which must
+    /// always respect the full duration.
+    fn _wait_triggered(&self, status: &str, duration: std::time::Duration) {
+        self._wait(status, duration);
+    }
+}
+
+/// Lets a boxed [IntelligentWait] be used wherever an owned one is expected, so
+/// `main` can choose between a [TimedTrigger]- and a [crate::triggering::FileWatchTrigger]-backed
+/// [DefaultWaiter] at runtime.
+impl IntelligentWait for Box<dyn IntelligentWait> {
+    fn success(&mut self) {
+        (**self).success();
+    }
+    fn skipped(&mut self) {
+        (**self).skipped();
+    }
+    fn limit_reached(&mut self) {
+        (**self).limit_reached();
+    }
+    fn error(&mut self) -> Result<(), WaitingGaveUp> {
+        (**self).error()
+    }
 }
 
-/// Default implementation of [IntelligentWait] that exposes some parameters
-pub struct DefaultWaiter {
+/// Default implementation of [IntelligentWait] that exposes some parameters.
+///
+/// Generic over a [Trigger] used for the normal polling cadence; defaults to
+/// [TimedTrigger] (fixed-interval polling). Use [DefaultWaiter::with_trigger] to drive it
+/// from file events instead, e.g. with [crate::triggering::FileWatchTrigger].
+pub struct DefaultWaiter<T: Trigger = TimedTrigger> {
     after_success: Duration,
     after_error: Duration,
     after_skipped: Duration,
     consecutive_errors: u32,
     consecutive_limits: u32,
     max_error_retry: u32,
+    trigger: T,
 }
-impl DefaultWaiter {
+impl DefaultWaiter<TimedTrigger> {
     pub fn new(
         after_success: Duration,
         after_error: Duration,
         after_skipped: Duration,
         max_error_retry: u32,
-    ) -> DefaultWaiter {
+    ) -> DefaultWaiter<TimedTrigger> {
+        DefaultWaiter::with_trigger(
+            after_success,
+            after_error,
+            after_skipped,
+            max_error_retry,
+            TimedTrigger,
+        )
+    }
+}
+impl<T: Trigger> DefaultWaiter<T> {
+    pub fn with_trigger(
+        after_success: Duration,
+        after_error: Duration,
+        after_skipped: Duration,
+        max_error_retry: u32,
+        trigger: T,
+    ) -> DefaultWaiter<T> {
         DefaultWaiter {
             after_success,
             after_error,
@@ -51,21 +101,22 @@ impl DefaultWaiter {
             max_error_retry,
             consecutive_errors: 0,
             consecutive_limits: 0,
+            trigger,
         }
     }
 }
-impl IntelligentWait for DefaultWaiter {
+impl<T: Trigger> IntelligentWait for DefaultWaiter<T> {
     /// Successful run: reset error and limit counters, wait [Self::after_success] long
     fn success(&mut self) {
         self.consecutive_errors = 0;
         self.consecutive_limits = 0;
-        self._wait("success", self.after_success);
+        self._wait_triggered("success", self.after_success);
     }
     /// Skipped run: reset error and limit counters, wait [Self::after_skipped] long
     fn skipped(&mut self) {
         self.consecutive_errors = 0;
         self.consecutive_limits = 0;
-        self._wait("skipped", self.after_skipped);
+        self._wait_triggered("skipped", self.after_skipped);
     }
     /// Rate limit reached, wait increasingly longer till next successfull run and then continue
     /// with previous normal duration.
@@ -105,4 +156,7 @@ impl IntelligentWait for DefaultWaiter {
         self._wait("error", dur);
         Ok(())
     }
+    fn _wait_triggered(&self, status: &str, duration: Duration) {
+        self.trigger.wait(status, duration);
+    }
 }